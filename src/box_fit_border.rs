@@ -1,8 +1,13 @@
 use bevy::prelude::*;
+use bevy::render::render_graph::base::camera::CAMERA_2D;
 
 use crate::{BoxFitProjection, SpriteQuad};
 
-/// Provides an opaque border around the desired resolution.
+/// Masks the window space outside the `virtual_width x virtual_height` image
+/// with opaque border sprites, so the area around the image reads as
+/// letterbox bars instead of uncovered game world. Bevy 0.5 has no
+/// camera-viewport clipping, so this overdraw is the only way to produce
+/// that effect.
 pub struct BoxFitBorderPlugin {
     pub color: Color,
 }
@@ -70,13 +75,29 @@ fn spawn_borders(
 
 fn resize_borders(
     cameras: Query<
-        (&BoxFitProjection, &Transform),
+        (&Camera, &BoxFitProjection, &Transform),
         Or<(Changed<BoxFitProjection>, Changed<Transform>)>,
     >,
     mut borders: Query<(&mut Sprite, &mut Transform, &Border), Without<BoxFitProjection>>,
 ) {
-    if let Some((projection, transform)) = cameras.iter().next() {
-        let z = projection.far - 0.2;
+    // Frame the first `BoxFitProjection` camera named `CAMERA_2D`, the name
+    // Bevy gives the window's own 2D camera and the one every
+    // `BoxFitCameraBundle` carries (including the window camera that
+    // `spawn_pixel_perfect_quad` spawns). A `BoxFitProjection` camera under
+    // any other name is skipped; if several are named `CAMERA_2D`, which one
+    // is framed is not defined.
+    let window_camera = cameras
+        .iter()
+        .find(|(camera, _, _)| camera.name.as_deref() == Some(CAMERA_2D));
+    if let Some((_, projection, transform)) = window_camera {
+        let z = projection.far() - 0.2;
+        // `pixel_aspect_ratio` only changes how many screen pixels a world
+        // unit covers on the x axis; it doesn't change how many world units
+        // the rendered image spans. So the borders, which live in world
+        // space, must still be sized from raw `virtual_width`, not the
+        // ratio-stretched `effective_virtual_width` - using the latter left
+        // an uncovered strip of game world between the image and the border
+        // whenever `pixel_aspect_ratio != 1.0`.
         let width = projection.virtual_width as f32;
         let height = projection.virtual_height as f32;
         let left = transform.translation.x
@@ -1,5 +1,23 @@
-use bevy::prelude::{Bundle, GlobalTransform, Mat4, Reflect, ReflectComponent, Transform};
-use bevy::render::camera::{Camera, CameraProjection, DepthCalculation, VisibleEntities};
+use bevy::app::{AppBuilder, CoreStage, Plugin};
+use bevy::prelude::{
+    Assets, Bundle, Color, ColorMaterial, Commands, GlobalTransform, Handle, IVec2, IntoSystem,
+    Mat4, Query, Res, Reflect, ReflectComponent, ResMut, Sprite, SpriteBundle, Transform, Vec2,
+};
+use bevy::render::camera::{
+    ActiveCameras, Camera, CameraProjection, DepthCalculation, VisibleEntities,
+};
+use bevy::render::pass::{
+    LoadOp, Operations, PassDescriptor, RenderPassColorAttachmentDescriptor,
+    RenderPassDepthStencilAttachmentDescriptor, TextureAttachment,
+};
+use bevy::render::render_graph::base::camera::CAMERA_2D;
+use bevy::render::render_graph::{base, CameraNode, PassNode, RenderGraph, TextureNode};
+use bevy::render::texture::{
+    Extent3d, FilterMode, SamplerDescriptor, Texture, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureUsage,
+};
+
+use crate::SpriteQuad;
 
 /// Provides the components for the camera entity.
 #[derive(Bundle)]
@@ -15,11 +33,24 @@ impl BoxFitCameraBundle {
     /// Create a component bundle for a camera where the size of virtual pixels
     /// is automatically set to fit the specified resolution inside the window.
     pub fn from_resolution(width: i32, height: i32) -> Self {
-        let projection = BoxFitProjection::from_resolution(width, height);
+        Self::from_projection(BoxFitProjection::from_resolution(width, height))
+    }
+
+    /// Create a component bundle sized from a tile grid instead of raw
+    /// pixels, e.g. `from_tiles(20, 12, 16)` for a 20x12 grid of 16px tiles.
+    pub fn from_tiles(tile_count_x: i32, tile_count_y: i32, tile_size: i32) -> Self {
+        Self::from_projection(BoxFitProjection::from_tiles(
+            tile_count_x,
+            tile_count_y,
+            tile_size,
+        ))
+    }
+
+    fn from_projection(projection: BoxFitProjection) -> Self {
         let far = projection.far();
         Self {
             camera: Camera {
-                name: Some(bevy::render::render_graph::base::camera::CAMERA_2D.to_string()),
+                name: Some(CAMERA_2D.to_string()),
                 ..Default::default()
             },
             projection,
@@ -53,6 +84,45 @@ pub struct BoxFitProjection {
     // If true, (0, 0) is the pixel closest to the center of the window,
     // otherwise it's at bottom left.
     pub centered: bool,
+
+    /// Stretches the effective width of a virtual pixel by this ratio before
+    /// fitting it to the window, for content authored with non-square
+    /// pixels (e.g. NES/Genesis-style 256x224 frames meant to fill a 4:3
+    /// screen). `1.0` (the default) preserves square pixels.
+    pub pixel_aspect_ratio: f32,
+
+    /// When set, `TileSnapPlugin` snaps the camera's translation to whole
+    /// multiples of this many virtual pixels, keeping a tile grid aligned to
+    /// screen pixels instead of jittering between tiles while scrolling. Set
+    /// by `from_tiles`; `None` disables snapping. Without `TileSnapPlugin`
+    /// added to the app, no snapping happens.
+    pub tile_size: Option<i32>,
+
+    /// Controls whether `zoom` is floored to a whole number. Defaults to
+    /// `ScalingMode::IntegerFit`, which guarantees every virtual pixel is
+    /// the same size on screen at the cost of some unused window space;
+    /// `ScalingMode::FloatFit` fills the window exactly but can make pixels
+    /// fractionally, unevenly sized.
+    pub scaling: ScalingMode,
+}
+
+/// How `BoxFitProjection` picks `zoom` to fit `virtual_width`/`virtual_height`
+/// into the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScalingMode {
+    /// Floor `zoom` to the nearest integer (minimum `1`), so every virtual
+    /// pixel is a uniform whole number of screen pixels. This is the
+    /// defining requirement for crisp pixel art.
+    IntegerFit,
+    /// Use the exact fractional `zoom` that fills the window, which can
+    /// make virtual pixels unevenly sized.
+    FloatFit,
+}
+
+impl Default for ScalingMode {
+    fn default() -> Self {
+        ScalingMode::IntegerFit
+    }
 }
 
 impl BoxFitProjection {
@@ -68,11 +138,48 @@ impl BoxFitProjection {
             virtual_height: height,
             zoom: 1.0,
             centered: true,
+            pixel_aspect_ratio: 1.0,
+            tile_size: None,
+            scaling: ScalingMode::default(),
         };
         projection.update(width as f32, height as f32);
         projection
     }
 
+    /// Create a projection sized from a tile grid instead of raw pixels,
+    /// e.g. `from_tiles(20, 12, 16)` for a 20x12 grid of 16px tiles. Also
+    /// sets `tile_size`, so with `TileSnapPlugin` added the camera's
+    /// translation is snapped to tile boundaries and the grid stays
+    /// pixel-aligned while scrolling.
+    pub fn from_tiles(tile_count_x: i32, tile_count_y: i32, tile_size: i32) -> Self {
+        let mut projection = Self::from_resolution(tile_count_x * tile_size, tile_count_y * tile_size);
+        projection.tile_size = Some(tile_size);
+        projection
+    }
+
+    /// `virtual_width` stretched by `pixel_aspect_ratio` — how many screen
+    /// pixels wide the rendered image is per unit of `zoom`, not its
+    /// world-space extent (that's always plain `virtual_width`; see
+    /// `virtual_origin`). Used to pick `zoom` and to size the physical
+    /// viewport rectangle.
+    fn effective_virtual_width(&self) -> f32 {
+        self.virtual_width as f32 * self.pixel_aspect_ratio
+    }
+
+    /// Picks the zoom that fits `virtual_width x virtual_height` into
+    /// `window_width x window_height`, applying `scaling`. `virtual_width` is
+    /// measured via `effective_virtual_width` so a `pixel_aspect_ratio` other
+    /// than `1.0` is accounted for when deciding which axis constrains the
+    /// fit.
+    fn compute_zoom(&self, window_width: f32, window_height: f32) -> f32 {
+        let zoom = (window_width / self.effective_virtual_width())
+            .min(window_height / self.virtual_height as f32);
+        match self.scaling {
+            ScalingMode::IntegerFit => zoom.floor().max(1.0),
+            ScalingMode::FloatFit => zoom,
+        }
+    }
+
     pub fn left(&self) -> f32 {
         self.left
     }
@@ -94,6 +201,107 @@ impl BoxFitProjection {
     pub fn zoom(&self) -> f32 {
         self.zoom
     }
+
+    /// Computes the window sub-rectangle, in physical pixels, that the
+    /// `virtual_width x virtual_height` image occupies when centered in a
+    /// `window_width x window_height` window, at the `zoom` `scaling` picks.
+    /// Used internally by `screen_to_virtual`/`virtual_to_screen`; Bevy 0.5
+    /// has no camera-viewport clipping, so `BoxFitBorderPlugin` still masks
+    /// the surrounding window space with border sprites rather than this
+    /// rectangle being rendered to directly.
+    pub fn viewport(&self, window_width: f32, window_height: f32) -> Viewport {
+        let zoom = self.compute_zoom(window_width, window_height);
+        let width = (self.effective_virtual_width() * zoom) as u32;
+        let height = (self.virtual_height as f32 * zoom) as u32;
+        let x = (window_width as u32).saturating_sub(width) / 2;
+        let y = (window_height as u32).saturating_sub(height) / 2;
+        Viewport {
+            physical_position: bevy::math::UVec2::new(x, y),
+            physical_size: bevy::math::UVec2::new(width, height),
+        }
+    }
+
+    /// The world-space offset of the virtual image's bottom-left corner,
+    /// relative to the camera's own translation: `-virtual_width / 2,
+    /// -virtual_height / 2` when `centered` (since the image is centered on
+    /// the camera), or the origin otherwise. `self.left`/`self.bottom` are
+    /// the camera's full frustum half-extents, which include the letterbox
+    /// slop revealed by a non-exact `zoom` and so are the wrong thing to
+    /// offset by here. Raw `virtual_width`, not `effective_virtual_width`, is
+    /// the image's real world-space extent: `pixel_aspect_ratio` changes how
+    /// many screen pixels a world unit covers on the x axis, not how many
+    /// world units the image itself spans.
+    fn virtual_origin(&self) -> Vec2 {
+        if self.centered {
+            Vec2::new(
+                -(self.virtual_width as f32) / 2.0,
+                -(self.virtual_height as f32) / 2.0,
+            )
+        } else {
+            Vec2::new(0.0, 0.0)
+        }
+    }
+
+    /// Converts a cursor position in window space (origin bottom-left, as
+    /// reported by `Windows::cursor_position`) into an integer virtual pixel
+    /// coordinate, accounting for `zoom`, `centered`, the letterbox offset
+    /// and `camera_transform`'s translation. Returns `None` if the cursor is
+    /// outside the rendered viewport (i.e. over a letterbox bar).
+    pub fn screen_to_virtual(
+        &self,
+        cursor_position: Vec2,
+        window_size: Vec2,
+        camera_transform: &Transform,
+    ) -> Option<IVec2> {
+        let viewport = self.viewport(window_size.x, window_size.y);
+        let local = cursor_position - viewport.physical_position.as_vec2();
+        if local.x < 0.0
+            || local.y < 0.0
+            || local.x >= viewport.physical_size.x as f32
+            || local.y >= viewport.physical_size.y as f32
+        {
+            return None;
+        }
+        // Screen pixels per virtual pixel on each axis; only x is stretched
+        // by `pixel_aspect_ratio`.
+        let scale = Vec2::new(
+            viewport.physical_size.x as f32 / self.virtual_width as f32,
+            viewport.physical_size.y as f32 / self.virtual_height as f32,
+        );
+        let translation = Vec2::new(camera_transform.translation.x, camera_transform.translation.y);
+        let virtual_pos = local / scale + translation + self.virtual_origin();
+        Some(IVec2::new(virtual_pos.x.floor() as i32, virtual_pos.y.floor() as i32))
+    }
+
+    /// Converts an integer virtual pixel coordinate into a window-space
+    /// cursor position (origin bottom-left), accounting for
+    /// `camera_transform`'s translation. This is the inverse of
+    /// `screen_to_virtual`.
+    pub fn virtual_to_screen(
+        &self,
+        virtual_position: IVec2,
+        window_size: Vec2,
+        camera_transform: &Transform,
+    ) -> Vec2 {
+        let viewport = self.viewport(window_size.x, window_size.y);
+        let scale = Vec2::new(
+            viewport.physical_size.x as f32 / self.virtual_width as f32,
+            viewport.physical_size.y as f32 / self.virtual_height as f32,
+        );
+        let virtual_pos = Vec2::new(virtual_position.x as f32, virtual_position.y as f32);
+        let translation = Vec2::new(camera_transform.translation.x, camera_transform.translation.y);
+        let local = virtual_pos - translation - self.virtual_origin();
+        local * scale + viewport.physical_position.as_vec2()
+    }
+}
+
+/// A window sub-rectangle, in physical pixels, that the
+/// `virtual_width x virtual_height` image occupies once scaled and
+/// centered in the window.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct Viewport {
+    pub physical_position: bevy::math::UVec2,
+    pub physical_size: bevy::math::UVec2,
 }
 
 impl CameraProjection for BoxFitProjection {
@@ -109,12 +317,13 @@ impl CameraProjection for BoxFitProjection {
     }
 
     fn update(&mut self, width: f32, height: f32) {
-        let zoom_x = width / self.virtual_width as f32;
-        let zoom_y = height / self.virtual_height as f32;
-        self.zoom = zoom_x.min(zoom_y);
+        self.zoom = self.compute_zoom(width, height);
 
-        let actual_width = width / (self.zoom as f32);
-        let actual_height = height / (self.zoom as f32);
+        // `pixel_aspect_ratio` stretches only the x axis, so a virtual pixel
+        // spans `zoom * pixel_aspect_ratio` screen pixels horizontally but
+        // only `zoom` vertically, instead of `zoom` on both axes.
+        let actual_width = width / (self.zoom * self.pixel_aspect_ratio);
+        let actual_height = height / self.zoom;
 
         if self.centered {
             self.left = -((actual_width as i32) / 2) as f32;
@@ -139,3 +348,447 @@ impl Default for BoxFitProjection {
         BoxFitProjection::from_resolution(640, 480)
     }
 }
+
+/// Orthographic projection fixed at exactly `virtual_width x virtual_height`
+/// world units, regardless of the window (or render target) it's attached
+/// to. Bevy 0.5's `camera_system` calls `CameraProjection::update` with the
+/// real window's size as soon as the camera is spawned and on every resize;
+/// a `BoxFitProjection` would refit its frustum to that window size, which
+/// is exactly wrong for the offscreen camera in `PixelPerfectCameraBundle` —
+/// that camera always renders into a render target that's a fixed
+/// `virtual_width x virtual_height` texture, so its frustum must stay
+/// pinned to that resolution no matter how big the real window is.
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct FixedSizeProjection {
+    left: f32,
+    right: f32,
+    bottom: f32,
+    top: f32,
+    near: f32,
+    far: f32,
+}
+
+impl FixedSizeProjection {
+    pub fn new(width: i32, height: i32) -> Self {
+        Self {
+            left: -(width as f32) / 2.0,
+            right: width as f32 / 2.0,
+            bottom: -(height as f32) / 2.0,
+            top: height as f32 / 2.0,
+            near: 0.0,
+            far: 1000.0,
+        }
+    }
+
+    pub fn far(&self) -> f32 {
+        self.far
+    }
+}
+
+impl CameraProjection for FixedSizeProjection {
+    fn get_projection_matrix(&self) -> Mat4 {
+        Mat4::orthographic_rh(
+            self.left,
+            self.right,
+            self.bottom,
+            self.top,
+            self.near,
+            self.far,
+        )
+    }
+
+    // Deliberately ignores `width`/`height`: the frustum is fixed at
+    // construction time and must not track the window or render target size.
+    fn update(&mut self, _width: f32, _height: f32) {}
+
+    fn depth_calculation(&self) -> DepthCalculation {
+        DepthCalculation::ZDifference
+    }
+}
+
+/// Name of the camera that renders the game world into the offscreen
+/// pixel-perfect render target, as opposed to `CAMERA_2D` which renders the
+/// upscaled quad to the window.
+pub const PIXEL_PERFECT_CAMERA: &str = "pixel_perfect_camera";
+
+// Render graph node names for the offscreen pass.
+const PIXEL_PERFECT_PASS: &str = "pixel_perfect_pass";
+const PIXEL_PERFECT_COLOR_TEXTURE: &str = "pixel_perfect_color_texture";
+const PIXEL_PERFECT_DEPTH_TEXTURE: &str = "pixel_perfect_depth_texture";
+
+/// Since Bevy 0.5 has no render-layer mechanism, a camera's `VisibleEntities`
+/// is populated purely by spatial frustum culling: every camera draws
+/// whatever falls inside its own projection bounds, with no way to tag
+/// entities as belonging to one pass or the other. To keep the game world
+/// out of the window's view and the upscaled quad out of the offscreen
+/// camera's view, the quad (and the window camera that draws it) live at
+/// this huge world-space offset, far outside any reasonable virtual
+/// resolution, so the two frustums never overlap.
+const PIXEL_PERFECT_QUAD_SPACE: f32 = 1_000_000.0;
+
+/// Provides the components for a camera that renders into an offscreen
+/// texture sized exactly `virtual_width x virtual_height`, with
+/// nearest-neighbor sampling. `PixelPerfectCameraPlugin` spawns this bundle
+/// itself, together with the quad that blits that texture to the window
+/// scaled by an integer factor; to spawn it by hand instead, pass the
+/// texture handle from `new` to `spawn_pixel_perfect_quad`.
+/// Unlike `BoxFitCameraBundle`, which scales geometry in place by `zoom`
+/// (fractional under `ScalingMode::FloatFit`), this guarantees every virtual pixel is sampled as a
+/// uniform, shimmer-free block. Uses `FixedSizeProjection`, not
+/// `BoxFitProjection`, since this camera's frustum must stay pinned to the
+/// virtual resolution regardless of the real window's size.
+#[derive(Bundle)]
+pub struct PixelPerfectCameraBundle {
+    pub camera: Camera,
+    pub projection: FixedSizeProjection,
+    pub visible_entities: VisibleEntities,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+impl PixelPerfectCameraBundle {
+    /// Create the offscreen world camera, wire it into a new render-graph
+    /// pass that renders into a virtual-resolution texture, and return the
+    /// texture handle. Hand that handle to `spawn_pixel_perfect_quad` to
+    /// create the upscaled full-screen quad that blits it to the window.
+    pub fn new(
+        width: i32,
+        height: i32,
+        textures: &mut Assets<Texture>,
+        active_cameras: &mut ActiveCameras,
+        render_graph: &mut RenderGraph,
+    ) -> (Self, Handle<Texture>) {
+        let size = Extent3d::new(width as u32, height as u32, 1);
+
+        let texture = textures.add(Texture {
+            data: vec![0; (width * height * 4) as usize],
+            size,
+            format: TextureFormat::Bgra8UnormSrgb,
+            dimension: TextureDimension::D2,
+            sampler: SamplerDescriptor {
+                mag_filter: FilterMode::Nearest,
+                min_filter: FilterMode::Nearest,
+                mipmap_filter: FilterMode::Nearest,
+                ..Default::default()
+            },
+        });
+
+        render_graph.add_node(
+            PIXEL_PERFECT_COLOR_TEXTURE,
+            TextureNode::new(
+                TextureDescriptor {
+                    size,
+                    format: TextureFormat::Bgra8UnormSrgb,
+                    usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
+                    ..Default::default()
+                },
+                Some(SamplerDescriptor {
+                    mag_filter: FilterMode::Nearest,
+                    min_filter: FilterMode::Nearest,
+                    mipmap_filter: FilterMode::Nearest,
+                    ..Default::default()
+                }),
+                None,
+            ),
+        );
+        render_graph.add_node(
+            PIXEL_PERFECT_DEPTH_TEXTURE,
+            TextureNode::new(
+                TextureDescriptor {
+                    size,
+                    format: TextureFormat::Depth32Float,
+                    usage: TextureUsage::OUTPUT_ATTACHMENT,
+                    ..Default::default()
+                },
+                None,
+                None,
+            ),
+        );
+
+        render_graph.add_system_node(PIXEL_PERFECT_CAMERA, CameraNode::new(PIXEL_PERFECT_CAMERA));
+
+        let mut pass = PassNode::<&Camera>::new(PassDescriptor {
+            color_attachments: vec![RenderPassColorAttachmentDescriptor {
+                attachment: TextureAttachment::Input("color_attachment".to_string()),
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::NONE),
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachmentDescriptor {
+                attachment: TextureAttachment::Input("depth".to_string()),
+                depth_ops: Some(Operations {
+                    load: LoadOp::Clear(1.0),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+            sample_count: 1,
+        });
+        pass.add_camera(PIXEL_PERFECT_CAMERA);
+        render_graph.add_node(PIXEL_PERFECT_PASS, pass);
+
+        render_graph
+            .add_slot_edge(
+                PIXEL_PERFECT_COLOR_TEXTURE,
+                TextureNode::TEXTURE,
+                PIXEL_PERFECT_PASS,
+                "color_attachment",
+            )
+            .unwrap();
+        render_graph
+            .add_slot_edge(
+                PIXEL_PERFECT_DEPTH_TEXTURE,
+                TextureNode::TEXTURE,
+                PIXEL_PERFECT_PASS,
+                "depth",
+            )
+            .unwrap();
+        render_graph
+            .add_node_edge(PIXEL_PERFECT_CAMERA, PIXEL_PERFECT_PASS)
+            .unwrap();
+        render_graph
+            .add_node_edge(PIXEL_PERFECT_PASS, base::node::MAIN_PASS)
+            .unwrap();
+
+        active_cameras.add(PIXEL_PERFECT_CAMERA);
+
+        let projection = FixedSizeProjection::new(width, height);
+        let far = projection.far();
+        let bundle = Self {
+            camera: Camera {
+                name: Some(PIXEL_PERFECT_CAMERA.to_string()),
+                ..Default::default()
+            },
+            projection,
+            visible_entities: Default::default(),
+            transform: Transform::from_xyz(0.0, 0.0, far - 0.1),
+            global_transform: Default::default(),
+        };
+        (bundle, texture)
+    }
+}
+
+// Component marking the sprite quad that blits the offscreen render target
+// to the window.
+pub struct PixelPerfectQuad;
+
+/// Spawns the full-screen sprite that upscales the offscreen render target
+/// texture, along with the dedicated window `CAMERA_2D` that draws it. That
+/// camera is a `BoxFitCameraBundle::from_resolution(virtual_width,
+/// virtual_height)`, so its own `BoxFitProjection` already fits the
+/// `virtual_width x virtual_height` quad to the window (integer zoom,
+/// centered, letterboxed) with no further scaling needed. Both the quad and
+/// its camera live at `PIXEL_PERFECT_QUAD_SPACE`, well outside the offscreen
+/// camera's frustum, so the quad never appears in its own source texture.
+pub fn spawn_pixel_perfect_quad(
+    commands: &mut Commands,
+    materials: &mut Assets<ColorMaterial>,
+    quad: &SpriteQuad,
+    texture: Handle<Texture>,
+    virtual_width: i32,
+    virtual_height: i32,
+) {
+    let mut window_camera = BoxFitCameraBundle::from_resolution(virtual_width, virtual_height);
+    window_camera.transform.translation.x += PIXEL_PERFECT_QUAD_SPACE;
+    window_camera.transform.translation.y += PIXEL_PERFECT_QUAD_SPACE;
+    commands.spawn().insert_bundle(window_camera);
+
+    commands
+        .spawn()
+        .insert(PixelPerfectQuad)
+        .insert_bundle(SpriteBundle {
+            material: materials.add(texture.into()),
+            mesh: quad.clone().into(),
+            sprite: Sprite::new(Vec2::new(virtual_width as f32, virtual_height as f32)),
+            transform: Transform::from_xyz(PIXEL_PERFECT_QUAD_SPACE, PIXEL_PERFECT_QUAD_SPACE, 0.0),
+            ..Default::default()
+        });
+}
+
+/// Plugin that spawns the offscreen pixel-perfect camera and its upscaled
+/// full-screen quad at startup. Use this instead of `BoxFitCameraBundle`
+/// when sub-pixel shimmer during camera movement is unacceptable.
+pub struct PixelPerfectCameraPlugin {
+    pub virtual_width: i32,
+    pub virtual_height: i32,
+}
+
+impl Plugin for PixelPerfectCameraPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let virtual_width = self.virtual_width;
+        let virtual_height = self.virtual_height;
+        app.add_startup_system(
+            (move |mut commands: Commands,
+                   mut active_cameras: ResMut<ActiveCameras>,
+                   mut render_graph: ResMut<RenderGraph>,
+                   mut textures: ResMut<Assets<Texture>>,
+                   mut materials: ResMut<Assets<ColorMaterial>>,
+                   quad: Res<SpriteQuad>| {
+                let (bundle, texture) = PixelPerfectCameraBundle::new(
+                    virtual_width,
+                    virtual_height,
+                    &mut textures,
+                    &mut active_cameras,
+                    &mut render_graph,
+                );
+                commands.spawn().insert_bundle(bundle);
+                spawn_pixel_perfect_quad(
+                    &mut commands,
+                    &mut materials,
+                    &quad,
+                    texture,
+                    virtual_width,
+                    virtual_height,
+                );
+            })
+            .system(),
+        );
+    }
+}
+
+/// Plugin that snaps a `BoxFitProjection` camera's translation to whole
+/// tile boundaries each frame, for cameras configured with `from_tiles`.
+/// Without this, scrolling by sub-tile amounts makes the tile grid jitter
+/// against the virtual pixel grid.
+pub struct TileSnapPlugin;
+
+impl Plugin for TileSnapPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_system_to_stage(CoreStage::PostUpdate, snap_to_tile_grid.system());
+    }
+}
+
+fn snap_to_tile_grid(mut cameras: Query<(&BoxFitProjection, &mut Transform)>) {
+    for (projection, mut transform) in cameras.iter_mut() {
+        if let Some(tile_size) = projection.tile_size {
+            let tile_size = tile_size as f32;
+            transform.translation.x = (transform.translation.x / tile_size).round() * tile_size;
+            transform.translation.y = (transform.translation.y / tile_size).round() * tile_size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn screen_to_virtual_uses_unstretched_width_with_non_square_pixels() {
+        // pixel_aspect_ratio only changes screen pixels per world unit, not
+        // the world-space extent of the image, so the window corner should
+        // still map to the same virtual pixel as the par = 1.0 case.
+        let mut projection = BoxFitProjection::from_resolution(100, 50);
+        projection.pixel_aspect_ratio = 2.0;
+        let window = Vec2::new(200.0, 50.0);
+        let transform = Transform::default();
+
+        assert_eq!(
+            projection.screen_to_virtual(Vec2::new(0.0, 0.0), window, &transform),
+            Some(IVec2::new(-50, -25))
+        );
+    }
+
+    #[test]
+    fn screen_to_virtual_round_trips_through_virtual_to_screen() {
+        let projection = BoxFitProjection::from_resolution(100, 50);
+        let window = Vec2::new(100.0, 50.0);
+        let transform = Transform::default();
+
+        let virtual_position = IVec2::new(10, 5);
+        let screen_position = projection.virtual_to_screen(virtual_position, window, &transform);
+        let round_tripped = projection
+            .screen_to_virtual(screen_position, window, &transform)
+            .expect("exact-fit window should never be in the letterbox");
+
+        assert_eq!(round_tripped, virtual_position);
+    }
+
+    #[test]
+    fn screen_to_virtual_maps_window_corners_to_virtual_extents() {
+        let projection = BoxFitProjection::from_resolution(100, 50);
+        let window = Vec2::new(100.0, 50.0);
+        let transform = Transform::default();
+
+        // Bottom-left of the window is the bottom-left virtual pixel.
+        assert_eq!(
+            projection.screen_to_virtual(Vec2::new(0.0, 0.0), window, &transform),
+            Some(IVec2::new(-50, -25))
+        );
+        // Just inside the top-right corner is the last virtual pixel.
+        assert_eq!(
+            projection.screen_to_virtual(Vec2::new(99.9, 49.9), window, &transform),
+            Some(IVec2::new(49, 24))
+        );
+    }
+
+    #[test]
+    fn screen_to_virtual_returns_none_in_the_letterbox_bars() {
+        // 300x50 window around a 100x50 virtual image leaves 100px bars on
+        // each side at zoom 1.
+        let projection = BoxFitProjection::from_resolution(100, 50);
+        let window = Vec2::new(300.0, 50.0);
+        let transform = Transform::default();
+
+        assert_eq!(
+            projection.screen_to_virtual(Vec2::new(50.0, 25.0), window, &transform),
+            None
+        );
+        assert_eq!(
+            projection.screen_to_virtual(Vec2::new(100.0, 0.0), window, &transform),
+            Some(IVec2::new(-50, -25))
+        );
+    }
+
+    #[test]
+    fn screen_to_virtual_accounts_for_camera_translation() {
+        let projection = BoxFitProjection::from_resolution(100, 50);
+        let window = Vec2::new(100.0, 50.0);
+        let transform = Transform::from_xyz(20.0, 0.0, 0.0);
+
+        // The camera scrolled 20 world units right, so the same window
+        // corner now reads 20 virtual pixels further right than it would at
+        // the origin.
+        assert_eq!(
+            projection.screen_to_virtual(Vec2::new(0.0, 0.0), window, &transform),
+            Some(IVec2::new(-30, -25))
+        );
+    }
+
+    #[test]
+    fn integer_fit_floors_zoom_and_clamps_to_one() {
+        let mut projection = BoxFitProjection::from_resolution(640, 480);
+
+        // min(1365/640, 768/480) = min(2.132.., 1.6) = 1.6, floored to 1.
+        projection.update(1365.0, 768.0);
+        assert_eq!(projection.zoom(), 1.0);
+
+        // A window smaller than the virtual resolution still floors to a
+        // whole pixel, not zero.
+        projection.update(100.0, 100.0);
+        assert_eq!(projection.zoom(), 1.0);
+    }
+
+    #[test]
+    fn float_fit_keeps_the_fractional_zoom() {
+        let mut projection = BoxFitProjection::from_resolution(640, 480);
+        projection.scaling = ScalingMode::FloatFit;
+
+        projection.update(1365.0, 768.0);
+        assert!((projection.zoom() - 1.6).abs() < 1e-4);
+    }
+
+    #[test]
+    fn viewport_letterboxes_the_unconstrained_axis_under_integer_fit() {
+        // 1365x768 window, 640x480 virtual image: height is the binding
+        // constraint (zoom floors to 1), so the full virtual width fits with
+        // slop left over on the sides for the border to mask.
+        let projection = BoxFitProjection::from_resolution(640, 480);
+        let viewport = projection.viewport(1365.0, 768.0);
+
+        assert_eq!(viewport.physical_size, bevy::math::UVec2::new(640, 480));
+        assert_eq!(viewport.physical_position, bevy::math::UVec2::new(362, 144));
+    }
+}